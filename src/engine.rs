@@ -0,0 +1,6 @@
+pub mod board;
+pub mod board_generator;
+pub mod exact_cover;
+pub mod game;
+pub mod hashsetnum;
+pub mod solver;
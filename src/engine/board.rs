@@ -1,25 +1,33 @@
 use std::borrow::Borrow;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+use thiserror::Error;
 use crate::engine::hashsetnum::SudokuHashSet;
 
+#[derive(Error, Debug)]
+pub enum ParseBoardError {
+    #[error("expected {expected} characters, found {found}")]
+    InvalidLength { expected: usize, found: usize },
+    #[error("invalid character '{0}'")]
+    InvalidCharacter(char),
+}
+
 #[derive(Debug, Copy, Clone)]
 pub enum Tile {
     Empty,
     Filled(u8)
 }
 
+/// A board with a block dimension of `N`, i.e. `N * N` cells per side and blocks
+/// that are `N` by `N`. Classic 9x9 Sudoku is `N = 3`; `N = 2` is a 4x4 grid and
+/// `N = 4` a 16x16 grid.
 #[derive(Clone)]
-pub struct Board {
-    rows: [[Tile; BOARD_SIZE as usize]; BOARD_SIZE as usize],
-    columns: [[Tile; BOARD_SIZE as usize]; BOARD_SIZE as usize],
-    blocks: [[Tile; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+pub struct Board<const N: usize> {
+    rows: Vec<Vec<Tile>>,
+    columns: Vec<Vec<Tile>>,
+    blocks: Vec<Vec<Tile>>,
 }
 
-#[derive(Copy, Clone)]
-pub struct BlockCoord;
-#[derive(Copy, Clone)]
-pub struct TileCoord;
-
 #[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
 pub struct Coord {
     pub x: u8,
@@ -34,28 +42,33 @@ impl Coord {
         }
     }
 
-    pub fn to_vec_position(&self) -> usize {
-        (self.y * BOARD_SIZE + self.x) as usize
-    }
-
-    pub fn to_block_index(&self) -> usize {
-        (self.y / BLOCK_SIZE * BLOCK_SIZE + self.x / BLOCK_SIZE) as usize
+    pub fn to_block_index<const N: usize>(self) -> usize {
+        (self.y as usize / N) * N + self.x as usize / N
     }
 
-    pub fn to_index_in_block(&self) -> usize {
-        (self.y % BLOCK_SIZE * BLOCK_SIZE + self.x % BLOCK_SIZE) as usize
+    pub fn to_index_in_block<const N: usize>(self) -> usize {
+        (self.y as usize % N) * N + self.x as usize % N
     }
 }
 
-pub const BOARD_SIZE: u8 = 9;
-pub const BLOCK_SIZE: u8 = 3;
+impl<const N: usize> Board<N> {
+    /// The number of cells per side, `N * N`.
+    pub const SIDE: usize = N * N;
+
+    pub fn new(storage: Vec<Tile>) -> Board<N> {
+        let mut board = Board::default();
+        for (position, tile) in storage.into_iter().enumerate() {
+            let coord = Coord::new((position % Self::SIDE) as u8, (position / Self::SIDE) as u8);
+            board.set_tile_in_place(&coord, tile);
+        }
+        board
+    }
 
-impl Board {
     pub fn get_tile(&self, coord: &Coord) -> &Tile {
         &self.rows[coord.y as usize][coord.x as usize]
     }
 
-    pub fn set_tile(&self, coord: &Coord, value: Tile) -> Board {
+    pub fn set_tile(&self, coord: &Coord, value: Tile) -> Board<N> {
         let mut result = self.to_owned();
         result.set_tile_in_place(coord, value);
         result
@@ -64,7 +77,7 @@ impl Board {
     pub fn set_tile_in_place(&mut self, coord: &Coord, value: Tile) {
         self.rows[coord.y as usize][coord.x as usize] = value;
         self.columns[coord.x as usize][coord.y as usize] = value;
-        self.blocks[coord.to_block_index()][coord.to_index_in_block()] = value;
+        self.blocks[coord.to_block_index::<N>()][coord.to_index_in_block::<N>()] = value;
     }
 
     pub fn get_row_for_coord(&self, coord: &Coord) -> &[Tile] {
@@ -76,27 +89,27 @@ impl Board {
     }
 
     pub fn get_block_for_coord(&self, coord: &Coord) -> &[Tile] {
-        &self.columns[(coord.x as usize * coord.y as usize) / BOARD_SIZE as usize]
+        &self.blocks[coord.to_block_index::<N>()]
     }
 
     pub fn verify_board(&self) -> bool {
         // Verify rows
-        for row in 0..BOARD_SIZE {
-            if !Board::is_valid_tile_set(self.get_row_for_coord(&Coord::new(0, row))) {
+        for row in 0..Self::SIDE as u8 {
+            if !Self::is_valid_tile_set(self.get_row_for_coord(&Coord::new(0, row))) {
                 return false;
             }
         }
 
         // Verify columns
-        for col in 0..BOARD_SIZE {
-            if !Board::is_valid_tile_set(&self.get_column_for_coord(&Coord::new(col, 0))) {
+        for col in 0..Self::SIDE as u8 {
+            if !Self::is_valid_tile_set(self.get_column_for_coord(&Coord::new(col, 0))) {
                 return false;
             }
         }
 
         // Verify squares
-        for block in 0..BOARD_SIZE {
-            if !Board::is_valid_tile_set(&self.blocks[block as usize]) {
+        for block in 0..Self::SIDE {
+            if !Self::is_valid_tile_set(&self.blocks[block]) {
                 return false;
             }
         }
@@ -115,7 +128,7 @@ impl Board {
     }
 
     pub fn is_valid_tile_set<T: Borrow<Tile>>(tiles: &[T]) -> bool {
-        let mut seen_numbers = SudokuHashSet::new();
+        let mut seen_numbers = SudokuHashSet::<N>::new();
 
         for tile in tiles {
             match tile.borrow() {
@@ -134,9 +147,9 @@ impl Board {
     }
 
     pub fn get_filled_tile_coords(&self) -> Vec<Coord> {
-        let mut filled_coords = Vec::with_capacity(BOARD_SIZE as usize * BOARD_SIZE as usize);
-        for x in 0..BOARD_SIZE {
-            for y in 0..BOARD_SIZE {
+        let mut filled_coords = Vec::with_capacity(Self::SIDE * Self::SIDE);
+        for x in 0..Self::SIDE as u8 {
+            for y in 0..Self::SIDE as u8 {
                 let coord = Coord::new(x, y);
 
                 match self.get_tile(&coord) {
@@ -152,28 +165,67 @@ impl Board {
     }
 }
 
-impl Debug for Board {
+impl<const N: usize> Debug for Board<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "\n")?;
-        for row in 0..BOARD_SIZE {
-            for col in 0..BOARD_SIZE {
+        writeln!(f)?;
+        for row in 0..Self::SIDE as u8 {
+            for col in 0..Self::SIDE as u8 {
                 match self.get_tile(&Coord::new(col, row)) {
                     Tile::Empty => write!(f, " .")?,
                     Tile::Filled(value) => write!(f, " {}", value)?
                 }
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> FromStr for Board<N> {
+    type Err = ParseBoardError;
+
+    fn from_str(s: &str) -> Result<Board<N>, Self::Err> {
+        let expected = Self::SIDE * Self::SIDE;
+        let chars: Vec<char> = s.chars().collect();
+        if chars.len() != expected {
+            return Err(ParseBoardError::InvalidLength { expected, found: chars.len() });
+        }
+
+        let mut storage = Vec::with_capacity(expected);
+        for c in chars {
+            let tile = match c {
+                '0' | '.' => Tile::Empty,
+                '1'..='9' => Tile::Filled(c as u8 - b'0'),
+                other => return Err(ParseBoardError::InvalidCharacter(other)),
+            };
+            storage.push(tile);
+        }
+
+        Ok(Board::new(storage))
+    }
+}
+
+impl<const N: usize> Display for Board<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for row in 0..Self::SIDE as u8 {
+            for col in 0..Self::SIDE as u8 {
+                match self.get_tile(&Coord::new(col, row)) {
+                    Tile::Empty => write!(f, ".")?,
+                    Tile::Filled(value) => write!(f, "{}", value)?,
+                }
+            }
         }
         Ok(())
     }
 }
 
-impl Default for Board {
-    fn default() -> Board {
+impl<const N: usize> Default for Board<N> {
+    fn default() -> Board<N> {
+        let side = N * N;
         Board {
-            columns: [[Tile::Empty; BOARD_SIZE as usize]; BOARD_SIZE as usize],
-            rows: [[Tile::Empty; BOARD_SIZE as usize]; BOARD_SIZE as usize],
-            blocks: [[Tile::Empty; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+            columns: vec![vec![Tile::Empty; side]; side],
+            rows: vec![vec![Tile::Empty; side]; side],
+            blocks: vec![vec![Tile::Empty; side]; side],
         }
     }
 }
@@ -191,7 +243,7 @@ mod tests {
             Tile::Filled(4), Tile::Filled(5), Tile::Filled(6),
             Tile::Filled(7), Tile::Filled(8), Tile::Filled(9)
         ];
-        assert!(Board::is_valid_tile_set(&tiles));
+        assert!(Board::<3>::is_valid_tile_set(&tiles));
     }
 
     #[test]
@@ -201,24 +253,47 @@ mod tests {
             Tile::Filled(4), Tile::Filled(5), Tile::Filled(6),
             Tile::Filled(7), Tile::Filled(9), Tile::Filled(9)
         ];
-        assert!(!Board::is_valid_tile_set(&tiles));
+        assert!(!Board::<3>::is_valid_tile_set(&tiles));
+    }
+
+    #[test]
+    fn parsed_board_round_trips() {
+        let grid = "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+        let board: Board<3> = grid.parse().unwrap();
+        assert!(board.verify_board());
+        assert_eq!(board.to_string(), grid);
+    }
+
+    #[test]
+    fn parses_empty_cells() {
+        let grid = ".3467891267219534819834256785976142342685379171392485696153728428741963534528617.";
+        let board: Board<3> = grid.parse().unwrap();
+        assert_eq!(board.to_string(), grid);
     }
 
     #[test]
     fn coord_to_block_index() {
         let coord = Coord::new(3, 3);
-        assert_eq!(coord.to_block_index(), 4);
+        assert_eq!(coord.to_block_index::<3>(), 4);
 
         let coord = Coord::new(2, 3);
-        assert_eq!(coord.to_block_index(), 3);
+        assert_eq!(coord.to_block_index::<3>(), 3);
     }
 
     #[test]
     fn coord_to_index_in_block() {
         let coord = Coord::new(3, 3);
-        assert_eq!(coord.to_index_in_block(), 0);
+        assert_eq!(coord.to_index_in_block::<3>(), 0);
 
         let coord = Coord::new(2, 3);
-        assert_eq!(coord.to_index_in_block(), 2);
+        assert_eq!(coord.to_index_in_block::<3>(), 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn coord_math_generalizes_to_four_by_four() {
+        // 4x4 board (N = 2): blocks are 2x2, so (2, 2) is the top-left of the last block.
+        let coord = Coord::new(2, 2);
+        assert_eq!(coord.to_block_index::<2>(), 3);
+        assert_eq!(coord.to_index_in_block::<2>(), 0);
+    }
+}
@@ -2,33 +2,33 @@ use std::borrow::Borrow;
 use rand::prelude::SliceRandom;
 use rand::{RngCore};
 use thiserror::Error;
-use crate::engine::board::{Board, BOARD_SIZE, Coord, Tile};
+use crate::engine::board::{Board, Coord, Tile};
+use crate::engine::exact_cover::count_solutions;
 use crate::engine::hashsetnum::SudokuHashSet;
+use crate::engine::solver::{solve_logically, Difficulty};
 
 #[derive(Error, Debug)]
-pub enum BoardGeneratorError {
+pub enum BoardGeneratorError<const N: usize> {
     #[error("No number available")]
     NoNumberAvailable,
-    #[error("Multiple solutions available")]
-    MultipleSolutionsAvailable,
     #[error("No more tiles to delete")]
-    NoDeletionsAvailable(Board)
+    NoDeletionsAvailable(Board<N>)
 }
 
-pub type BoardGeneratorResult<T> = Result<T, BoardGeneratorError>;
+pub type BoardGeneratorResult<T, const N: usize> = Result<T, BoardGeneratorError<N>>;
 
-pub struct BoardGenerator<Rng: RngCore> {
+pub struct BoardGenerator<Rng: RngCore, const N: usize> {
     rng: Rng,
 }
 
-impl<Rng: RngCore> BoardGenerator<Rng> {
-    pub fn new(rng: Rng) -> BoardGenerator<Rng> {
+impl<Rng: RngCore, const N: usize> BoardGenerator<Rng, N> {
+    pub fn new(rng: Rng) -> BoardGenerator<Rng, N> {
         BoardGenerator {
             rng
         }
     }
 
-    pub fn get_numbers_for_tiles<T: Borrow<Tile>>(tiles: &[T]) -> SudokuHashSet {
+    pub fn get_numbers_for_tiles<T: Borrow<Tile>>(tiles: &[T]) -> SudokuHashSet<N> {
         tiles.iter().filter_map(|tile| {
             match tile.borrow() {
                 Tile::Empty => None,
@@ -37,8 +37,8 @@ impl<Rng: RngCore> BoardGenerator<Rng> {
         }).collect()
     }
 
-    fn update_board_with_random_number(&mut self, board: &Board, coord: &Coord, excluding: &SudokuHashSet) -> BoardGeneratorResult<(u8, Board)> {
-        let mut nums: Vec<u8> = (1..BOARD_SIZE + 1).filter(|num| {
+    fn update_board_with_random_number(&mut self, board: &Board<N>, coord: &Coord, excluding: &SudokuHashSet<N>) -> BoardGeneratorResult<(u8, Board<N>), N> {
+        let mut nums: Vec<u8> = (1..=Board::<N>::SIDE as u8).filter(|num| {
             !excluding.contains(num)
         }).collect();
 
@@ -47,7 +47,7 @@ impl<Rng: RngCore> BoardGenerator<Rng> {
         let mut new_board = board.clone();
 
         for num in nums {
-            new_board.set_tile_in_place(&coord, Tile::Filled(num));
+            new_board.set_tile_in_place(coord, Tile::Filled(num));
 
             if new_board.verify_board() {
                 return Ok((num, new_board));
@@ -57,79 +57,50 @@ impl<Rng: RngCore> BoardGenerator<Rng> {
         Err(BoardGeneratorError::NoNumberAvailable)
     }
 
-    pub fn new_board(&mut self, desired_cells_given: usize) -> BoardGeneratorResult<(Board, Board)> {
+    pub fn new_board(&mut self, desired_cells_given: usize) -> BoardGeneratorResult<(Board<N>, Board<N>, Difficulty), N> {
         let solved_board = self.try_fill_board(Board::default()).unwrap();
 
         let emptied_board = self.try_empty_board(solved_board.clone(), desired_cells_given, Vec::new())?;
 
-        Ok((solved_board, emptied_board))
+        // Rate the puzzle by the hardest strategy a human solve needs for it.
+        let (_, difficulty) = solve_logically(&emptied_board);
+
+        Ok((solved_board, emptied_board, difficulty))
     }
 
-    fn try_empty_board(&mut self, mut board: Board, desired_cells_given: usize, mut unreplacable_coords: Vec<Coord>) -> BoardGeneratorResult<Board> {
+    fn try_empty_board(&mut self, board: Board<N>, desired_cells_given: usize, mut unreplacable_coords: Vec<Coord>) -> BoardGeneratorResult<Board<N>, N> {
+        if board.get_filled_tile_coords().len() == desired_cells_given {
+            return Ok(board);
+        }
+
         let mut filled_coords: Vec<Coord> = board.get_filled_tile_coords().into_iter().filter(|coord| {
            !unreplacable_coords.contains(coord)
         }).collect();
 
         filled_coords.shuffle(&mut self.rng);
 
-        // Try to replace the number with something else, see if it's valid, and see if we can still fill the board
-
-        let mut has_replaced_a_tile = false;
+        // Empty the first cell that leaves the puzzle with a single solution. Emptying a
+        // cell can only ever add solutions, so a cell that can't be removed now never can.
         for random_tile_coord in filled_coords {
-            let Tile::Filled(original_value) = board.get_tile(&random_tile_coord) else {
-                panic!("Empty tile received");
-            };
-
-            let is_replacable_by_something_else = {
-                let mut has_options = false;
-                for i in (1..BOARD_SIZE + 1).filter(|num| *num != *original_value) {
-                    let updated_board = board.set_tile(&random_tile_coord, Tile::Filled(i));
-
-                    if updated_board.verify_board() {
-                        match self.try_fill_board(updated_board) {
-                            Ok(_) => {
-                                unreplacable_coords.push(random_tile_coord.clone());
-                                has_options = true;
-                            },
-                            Err(BoardGeneratorError::MultipleSolutionsAvailable) => {
-                                unreplacable_coords.push(random_tile_coord.clone());
-                                has_options = true;
-                            },
-                            Err(BoardGeneratorError::NoNumberAvailable) => {},
-                            Err(err) => return Err(err)
-                        };
-                    }
-                }
-
-                has_options
-            };
+            let candidate = board.set_tile(&random_tile_coord, Tile::Empty);
 
-            if !is_replacable_by_something_else {
-                board = board.set_tile(&random_tile_coord, Tile::Empty);
-                has_replaced_a_tile = true;
-
-                break;
+            if count_solutions::<N>(&candidate, 2) == 1 {
+                return self.try_empty_board(candidate, desired_cells_given, unreplacable_coords);
             }
-        }
-
-        if !has_replaced_a_tile {
-            return Err(BoardGeneratorError::NoDeletionsAvailable(board))
-        }
 
-        if board.get_filled_tile_coords().len() == desired_cells_given {
-            return Ok(board);
+            unreplacable_coords.push(random_tile_coord);
         }
 
-        return self.try_empty_board(board, desired_cells_given, unreplacable_coords);
+        Err(BoardGeneratorError::NoDeletionsAvailable(board))
     }
 
-    pub fn try_fill_board(&mut self, board: Board) -> BoardGeneratorResult<Board> {
-        for x in (0..BOARD_SIZE).collect::<Vec<u8>>() {
-            for y in (0..BOARD_SIZE).collect::<Vec<u8>>() {
+    pub fn try_fill_board(&mut self, board: Board<N>) -> BoardGeneratorResult<Board<N>, N> {
+        for x in (0..Board::<N>::SIDE as u8).collect::<Vec<u8>>() {
+            for y in (0..Board::<N>::SIDE as u8).collect::<Vec<u8>>() {
                 let coord = Coord::new(x, y);
 
                 if let Tile::Empty = board.get_tile(&coord) {
-                    let mut excluding_numbers = SudokuHashSet::new();
+                    let mut excluding_numbers = SudokuHashSet::<N>::new();
                     loop {
                         let (num, board) = self.update_board_with_random_number(&board, &coord, &excluding_numbers)?;
 
@@ -153,4 +124,39 @@ impl<Rng: RngCore> BoardGenerator<Rng> {
 
         panic!("Shouldn't be reachable");
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    #[test]
+    fn generates_a_unique_four_by_four() {
+        let (solved, emptied, _) = BoardGenerator::<_, 2>::new(thread_rng()).new_board(8).unwrap();
+
+        assert!(solved.is_complete());
+        assert!(solved.verify_board());
+        assert_eq!(count_solutions::<2>(&emptied, 2), 1);
+    }
+
+    #[test]
+    fn generates_a_unique_nine_by_nine() {
+        let (solved, emptied, _) = BoardGenerator::<_, 3>::new(thread_rng()).new_board(35).unwrap();
+
+        assert!(solved.is_complete());
+        assert!(solved.verify_board());
+        assert_eq!(count_solutions::<3>(&emptied, 2), 1);
+    }
+
+    #[test]
+    fn fills_and_counts_a_sixteen_by_sixteen() {
+        let solved = BoardGenerator::<_, 4>::new(thread_rng())
+            .try_fill_board(Board::default())
+            .unwrap();
+
+        assert!(solved.is_complete());
+        assert!(solved.verify_board());
+        assert_eq!(count_solutions::<4>(&solved, 2), 1);
+    }
+}
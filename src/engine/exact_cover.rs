@@ -0,0 +1,252 @@
+use crate::engine::board::{Board, Tile};
+
+/// Count the number of complete solutions of `board`, stopping as soon as
+/// `limit` of them have been found.
+///
+/// The puzzle is modelled as an exact-cover problem with four families of
+/// constraints — every cell occupied, and every value present once per row,
+/// column and block — and solved with Knuth's Algorithm X using the Dancing
+/// Links representation. Returning early at `limit` makes the common "is this
+/// puzzle unique?" query (`limit == 2`) cheap.
+pub fn count_solutions<const N: usize>(board: &Board<N>, limit: usize) -> usize {
+    if !board.verify_board() {
+        return 0;
+    }
+
+    let mut links = DancingLinks::new(N * N, N);
+
+    // Force every given into the cover before searching for completions.
+    for coord in board.get_filled_tile_coords() {
+        if let Tile::Filled(value) = board.get_tile(&coord) {
+            links.select_given(coord.x as usize, coord.y as usize, *value as usize);
+        }
+    }
+
+    let mut count = 0;
+    links.search(limit, &mut count);
+    count
+}
+
+struct DancingLinks {
+    side: usize,
+    block: usize,
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    size: Vec<usize>,
+    root: usize,
+}
+
+impl DancingLinks {
+    fn new(side: usize, block: usize) -> DancingLinks {
+        let constraints = 4 * side * side;
+        let mut links = DancingLinks {
+            side,
+            block,
+            left: Vec::new(),
+            right: Vec::new(),
+            up: Vec::new(),
+            down: Vec::new(),
+            column: Vec::new(),
+            size: Vec::new(),
+            root: constraints,
+        };
+
+        // Column headers plus a root header, linked in a horizontal ring.
+        for _ in 0..=constraints {
+            links.push_node();
+        }
+        for header in 0..=constraints {
+            links.left[header] = if header == 0 { constraints } else { header - 1 };
+            links.right[header] = if header == constraints { 0 } else { header + 1 };
+            links.up[header] = header;
+            links.down[header] = header;
+            links.column[header] = header;
+        }
+
+        for row in 0..side {
+            for col in 0..side {
+                for value in 1..=side {
+                    links.add_placement(row, col, value);
+                }
+            }
+        }
+
+        links
+    }
+
+    fn push_node(&mut self) -> usize {
+        let index = self.left.len();
+        self.left.push(index);
+        self.right.push(index);
+        self.up.push(index);
+        self.down.push(index);
+        self.column.push(index);
+        self.size.push(0);
+        index
+    }
+
+    fn columns_for(&self, row: usize, col: usize, value: usize) -> [usize; 4] {
+        let side = self.side;
+        let block = (row / self.block) * self.block + col / self.block;
+        let v = value - 1;
+        [
+            row * side + col,
+            side * side + row * side + v,
+            2 * side * side + col * side + v,
+            3 * side * side + block * side + v,
+        ]
+    }
+
+    fn add_placement(&mut self, row: usize, col: usize, value: usize) {
+        let mut first = None;
+        for header in self.columns_for(row, col, value) {
+            let node = self.push_node();
+            self.column[node] = header;
+
+            // Splice into the column above the header.
+            let up = self.up[header];
+            self.down[node] = header;
+            self.up[node] = up;
+            self.down[up] = node;
+            self.up[header] = node;
+            self.size[header] += 1;
+
+            match first {
+                None => {
+                    self.left[node] = node;
+                    self.right[node] = node;
+                    first = Some(node);
+                }
+                Some(first) => {
+                    let left = self.left[first];
+                    self.right[node] = first;
+                    self.left[node] = left;
+                    self.right[left] = node;
+                    self.left[first] = node;
+                }
+            }
+        }
+    }
+
+    fn cover(&mut self, header: usize) {
+        self.right[self.left[header]] = self.right[header];
+        self.left[self.right[header]] = self.left[header];
+
+        let mut row = self.down[header];
+        while row != header {
+            let mut node = self.right[row];
+            while node != row {
+                self.down[self.up[node]] = self.down[node];
+                self.up[self.down[node]] = self.up[node];
+                self.size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    fn uncover(&mut self, header: usize) {
+        let mut row = self.up[header];
+        while row != header {
+            let mut node = self.left[row];
+            while node != row {
+                self.size[self.column[node]] += 1;
+                self.down[self.up[node]] = node;
+                self.up[self.down[node]] = node;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+
+        self.right[self.left[header]] = header;
+        self.left[self.right[header]] = header;
+    }
+
+    /// Permanently commit the placement of a given by covering all of its
+    /// columns, mirroring the effect of selecting its row in Algorithm X.
+    fn select_given(&mut self, col: usize, row: usize, value: usize) {
+        for header in self.columns_for(row, col, value) {
+            self.cover(header);
+        }
+    }
+
+    fn search(&mut self, limit: usize, count: &mut usize) {
+        if self.right[self.root] == self.root {
+            *count += 1;
+            return;
+        }
+
+        let header = self.choose_column();
+        self.cover(header);
+
+        let mut row = self.down[header];
+        while row != header && *count < limit {
+            let mut node = self.right[row];
+            while node != row {
+                self.cover(self.column[node]);
+                node = self.right[node];
+            }
+
+            self.search(limit, count);
+
+            let mut node = self.left[row];
+            while node != row {
+                self.uncover(self.column[node]);
+                node = self.left[node];
+            }
+
+            row = self.down[row];
+        }
+
+        self.uncover(header);
+    }
+
+    fn choose_column(&self) -> usize {
+        let mut best = self.right[self.root];
+        let mut header = self.right[self.root];
+        while header != self.root {
+            if self.size[header] < self.size[best] {
+                best = header;
+            }
+            header = self.right[header];
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(grid: &str) -> Board<3> {
+        grid.parse().unwrap()
+    }
+
+    #[test]
+    fn solved_grid_is_unique() {
+        let solved = board("534678912672195348198342567859761423426853791713924856961537284287419635345286179");
+        assert_eq!(count_solutions(&solved, 2), 1);
+    }
+
+    #[test]
+    fn known_puzzle_is_unique() {
+        let puzzle = board("530070000600195000098000060800060003400803001700020006060000280000419005000080079");
+        assert_eq!(count_solutions(&puzzle, 2), 1);
+    }
+
+    #[test]
+    fn empty_board_has_many_solutions() {
+        let empty: Board<3> = ".".repeat(9 * 9).parse().unwrap();
+        assert_eq!(count_solutions(&empty, 2), 2);
+    }
+
+    #[test]
+    fn four_by_four_puzzle_is_unique() {
+        // A 4x4 grid (N = 2) with a single completion.
+        let puzzle: Board<2> = "1234341221434321".parse().unwrap();
+        assert_eq!(count_solutions(&puzzle, 2), 1);
+    }
+}
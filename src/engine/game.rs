@@ -1,42 +1,165 @@
-use crate::engine::board::Board;
+use crate::engine::board::{Board, Coord, ParseBoardError, Tile};
 use crate::engine::board_generator::{BoardGenerator, BoardGeneratorError};
+use crate::engine::solver::{solve_logically, Difficulty};
 
 use rand::prelude::*;
+use rand::rngs::ThreadRng;
 use thiserror::Error;
 
 #[derive(Debug, Default)]
-pub struct Game {
-    pub history: Vec<Board>,
-    pub current: Board,
-    pub solved: Board,
+pub struct Game<const N: usize> {
+    pub history: Vec<Board<N>>,
+    pub redo_history: Vec<Board<N>>,
+    pub current: Board<N>,
+    pub solved: Board<N>,
+    /// How hard the generated puzzle is, rated by the logical solver.
+    pub difficulty: Difficulty,
+}
+
+/// What happened when a value was placed via [`Game::set_cell`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MoveOutcome {
+    /// The value was placed and the board is still consistent.
+    Placed,
+    /// The value conflicts with the row, column or block it was placed in. Also
+    /// returned, with nothing placed and no history recorded, when the
+    /// coordinate or the value is out of range for the board.
+    Conflict,
+    /// The placement filled the final cell of a consistent board.
+    Solved,
 }
 
 #[derive(Error, Debug)]
-pub enum GameError {
+pub enum GameError<const N: usize> {
     #[error("Couldn't generate a new board. Amount of tries exceeded")]
     TriesExceeded,
     #[error("Couldn't generate a new board. {0}")]
-    BoardGeneratorError(#[from] BoardGeneratorError),
+    BoardGeneratorError(#[from] BoardGeneratorError<N>),
+    #[error("Couldn't parse a board. {0}")]
+    ParseBoardError(#[from] ParseBoardError),
 }
 
-pub type GameResult<T> = Result<T, GameError>;
+pub type GameResult<T, const N: usize> = Result<T, GameError<N>>;
 
-impl Game {
-    pub fn new_random(max_tries: usize, desired_cells_given: usize) -> GameResult<Game> {
-        for i in 0..max_tries {
-            if let Ok((solved, emptied)) = match BoardGenerator::new(thread_rng()).new_board(desired_cells_given) {
+impl<const N: usize> Game<N> {
+    pub fn new_random(max_tries: usize, desired_cells_given: usize) -> GameResult<Game<N>, N> {
+        for _ in 0..max_tries {
+            if let Ok((solved, emptied, difficulty)) = match BoardGenerator::<ThreadRng, N>::new(thread_rng()).new_board(desired_cells_given) {
                 Ok(board) => Ok(board),
                 Err(BoardGeneratorError::NoDeletionsAvailable(board)) => Err(BoardGeneratorError::NoDeletionsAvailable(board)),
                 Err(err) => return Err(err.into()),
             }{
                 return Ok(Game {
                     history: vec![],
+                    redo_history: vec![],
                     current: emptied,
-                    solved: solved,
+                    solved,
+                    difficulty,
                 })
             }
         }
 
         Err(GameError::TriesExceeded)
     }
+
+    pub fn from_strings(current: &str, solved: &str) -> GameResult<Game<N>, N> {
+        let current: Board<N> = current.parse()?;
+        let difficulty = solve_logically(&current).1;
+        Ok(Game {
+            history: vec![],
+            redo_history: vec![],
+            current,
+            solved: solved.parse()?,
+            difficulty,
+        })
+    }
+
+    pub fn to_strings(&self) -> (String, String) {
+        (self.current.to_string(), self.solved.to_string())
+    }
+
+    /// Place `value` at `coord`, remembering the previous board so the move can
+    /// be undone. The returned [`MoveOutcome`] says whether the move conflicts
+    /// with the rest of the board or completed the puzzle.
+    pub fn set_cell(&mut self, coord: Coord, value: u8) -> MoveOutcome {
+        if coord.x as usize >= Board::<N>::SIDE || coord.y as usize >= Board::<N>::SIDE {
+            return MoveOutcome::Conflict;
+        }
+
+        if value < 1 || value as usize > Board::<N>::SIDE {
+            return MoveOutcome::Conflict;
+        }
+
+        self.history.push(self.current.clone());
+        self.redo_history.clear();
+        self.current.set_tile_in_place(&coord, Tile::Filled(value));
+
+        if !self.current.verify_board() {
+            MoveOutcome::Conflict
+        } else if self.current.is_complete() {
+            MoveOutcome::Solved
+        } else {
+            MoveOutcome::Placed
+        }
+    }
+
+    /// Step one move back, returning whether there was anything to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(previous) => {
+                self.redo_history.push(std::mem::replace(&mut self.current, previous));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Step one move forward after an [`undo`](Self::undo), returning whether
+    /// there was anything to redo.
+    pub fn redo(&mut self) -> bool {
+        match self.redo_history.pop() {
+            Some(next) => {
+                self.history.push(std::mem::replace(&mut self.current, next));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether the current board matches the solution.
+    pub fn is_solved(&self) -> bool {
+        self.current.is_complete() && self.current.to_string() == self.solved.to_string()
+    }
+
+    /// Reveal one correct cell from the solution, returning the coordinate that
+    /// was filled, or `None` when the board is already complete.
+    pub fn hint(&mut self) -> Option<Coord> {
+        for y in 0..Board::<N>::SIDE as u8 {
+            for x in 0..Board::<N>::SIDE as u8 {
+                let coord = Coord::new(x, y);
+                if let Tile::Empty = self.current.get_tile(&coord) {
+                    let value = *self.solved.get_tile(&coord);
+                    self.history.push(self.current.clone());
+                    self.redo_history.clear();
+                    self.current.set_tile_in_place(&coord, value);
+                    return Some(coord);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_round_trip_through_game() {
+        let current = "53467891267219534819834256785976142342685379171392485696153728428741963534528617.";
+        let solved = "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+        let game = Game::<3>::from_strings(current, solved).unwrap();
+        assert_eq!(game.to_strings(), (current.to_string(), solved.to_string()));
+    }
 }
\ No newline at end of file
@@ -1,27 +1,78 @@
 use std::borrow::Borrow;
-use crate::engine::board::BOARD_SIZE;
 
-pub struct SudokuHashSet {
-    data: [bool; BOARD_SIZE as usize + 1],
+/// A set of Sudoku values (1..=`N * N`) stored as a single `u64` bitmask, where
+/// bit `n` is set when value `n` is present. Bit `0` is always unused. `N` is the
+/// board's block dimension, so the largest representable value is `N * N`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SudokuHashSet<const N: usize> {
+    data: u64,
 }
 
-impl SudokuHashSet {
-    pub fn new() -> SudokuHashSet {
+impl<const N: usize> SudokuHashSet<N> {
+    /// The number of distinct values, `N * N`.
+    pub const SIDE: usize = N * N;
+
+    pub fn new() -> SudokuHashSet<N> {
         SudokuHashSet {
-            data: [false; BOARD_SIZE as usize + 1],
+            data: 0,
         }
     }
 
     pub fn insert<T: Borrow<u8>>(&mut self, num: T) {
-        self.data[*num.borrow() as usize] = true;
+        self.data |= 1 << *num.borrow();
     }
 
     pub fn contains<T: Borrow<u8>>(&self, num: T) -> bool {
-        self.data[*num.borrow() as usize]
+        self.data & (1 << *num.borrow()) != 0
+    }
+
+    /// The values present in either set.
+    pub fn union(&self, other: &SudokuHashSet<N>) -> SudokuHashSet<N> {
+        SudokuHashSet { data: self.data | other.data }
+    }
+
+    /// The values present in both sets.
+    pub fn intersection(&self, other: &SudokuHashSet<N>) -> SudokuHashSet<N> {
+        SudokuHashSet { data: self.data & other.data }
+    }
+
+    /// The values in `self` that are not in `other`.
+    pub fn difference(&self, other: &SudokuHashSet<N>) -> SudokuHashSet<N> {
+        SudokuHashSet { data: self.data & !other.data }
+    }
+
+    /// The values in 1..=`N * N` that are absent from this set.
+    pub fn complement(&self) -> SudokuHashSet<N> {
+        SudokuHashSet { data: !self.data & Self::full_mask() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.count_ones() as usize
+    }
+
+    pub fn count_ones(&self) -> u32 {
+        self.data.count_ones()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data == 0
+    }
+
+    /// Iterate over the values contained in the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (1..=Self::SIDE as u8).filter(move |num| self.contains(num))
+    }
+
+    fn full_mask() -> u64 {
+        let mut mask = 0u64;
+        for num in 1..=Self::SIDE as u8 {
+            mask |= 1 << num;
+        }
+        mask
     }
 }
 
-impl FromIterator<u8> for SudokuHashSet {
+impl<const N: usize> FromIterator<u8> for SudokuHashSet<N> {
     fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
         let mut set = SudokuHashSet::new();
         for num in iter {
@@ -29,4 +80,50 @@ impl FromIterator<u8> for SudokuHashSet {
         }
         set
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_contains() {
+        let mut set = SudokuHashSet::<3>::new();
+        set.insert(4);
+        assert!(set.contains(4));
+        assert!(!set.contains(5));
+    }
+
+    #[test]
+    fn set_algebra() {
+        let a: SudokuHashSet<3> = [1, 2, 3].into_iter().collect();
+        let b: SudokuHashSet<3> = [3, 4, 5].into_iter().collect();
+
+        assert_eq!(a.union(&b), [1, 2, 3, 4, 5].into_iter().collect());
+        assert_eq!(a.intersection(&b), [3].into_iter().collect());
+        assert_eq!(a.difference(&b), [1, 2].into_iter().collect());
+    }
+
+    #[test]
+    fn complement_is_over_one_to_board_size() {
+        let set: SudokuHashSet<3> = (1..=9).collect();
+        assert!(set.complement().is_empty());
+        assert_eq!(set.len(), 9);
+
+        let empty = SudokuHashSet::<3>::new();
+        assert_eq!(empty.complement().len(), 9);
+    }
+
+    #[test]
+    fn complement_tracks_block_dimension() {
+        // 16x16 board (N = 4) has values 1..=16.
+        let empty = SudokuHashSet::<4>::new();
+        assert_eq!(empty.complement().len(), 16);
+    }
+
+    #[test]
+    fn iterates_in_ascending_order() {
+        let set: SudokuHashSet<3> = [7, 2, 5].into_iter().collect();
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![2, 5, 7]);
+    }
+}
@@ -0,0 +1,234 @@
+use rand::rngs::ThreadRng;
+use crate::engine::board::{Board, Coord, Tile};
+use crate::engine::board_generator::BoardGenerator;
+use crate::engine::hashsetnum::SudokuHashSet;
+
+/// The hardest strategy a logical solve had to reach, used to rate a puzzle.
+///
+/// Ordered from easiest to hardest so callers can `max` difficulties as the
+/// solve progresses; `RequiresGuessing` means candidate propagation stalled
+/// before the board was complete.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    #[default]
+    NakedSingle,
+    HiddenSingle,
+    NakedPair,
+    RequiresGuessing,
+}
+
+/// Solve `board` the way a person would, applying pencil-mark strategies to a
+/// fixpoint instead of backtracking. Returns the resulting board (fully solved
+/// when possible) together with the hardest strategy that was required.
+pub fn solve_logically<const N: usize>(board: &Board<N>) -> (Board<N>, Difficulty) {
+    let mut board = board.clone();
+    let mut hardest = Difficulty::NakedSingle;
+
+    loop {
+        if board.is_complete() {
+            return (board, hardest);
+        }
+
+        let candidates = compute_candidates(&board);
+
+        if let Some((coord, value)) = find_naked_single(&candidates) {
+            board.set_tile_in_place(&coord, Tile::Filled(value));
+            hardest = hardest.max(Difficulty::NakedSingle);
+            continue;
+        }
+
+        if let Some((coord, value)) = find_hidden_single(&board, &candidates) {
+            board.set_tile_in_place(&coord, Tile::Filled(value));
+            hardest = hardest.max(Difficulty::HiddenSingle);
+            continue;
+        }
+
+        // Nothing obvious left; try to eliminate candidates with naked pairs and
+        // see whether that exposes a fresh single.
+        let mut reduced = candidates.clone();
+        if eliminate_naked_pairs::<N>(&mut reduced) {
+            if let Some((coord, value)) = find_naked_single(&reduced)
+                .or_else(|| find_hidden_single(&board, &reduced))
+            {
+                board.set_tile_in_place(&coord, Tile::Filled(value));
+                hardest = hardest.max(Difficulty::NakedPair);
+                continue;
+            }
+        }
+
+        return (board, Difficulty::RequiresGuessing);
+    }
+}
+
+type Candidates<const N: usize> = Vec<Vec<SudokuHashSet<N>>>;
+
+/// Build the set of still-possible values for every empty cell by removing
+/// everything already filled in its row, column and block.
+fn compute_candidates<const N: usize>(board: &Board<N>) -> Candidates<N> {
+    let size = Board::<N>::SIDE;
+    let mut candidates = vec![vec![SudokuHashSet::new(); size]; size];
+
+    for y in 0..size as u8 {
+        for x in 0..size as u8 {
+            let coord = Coord::new(x, y);
+            if let Tile::Empty = board.get_tile(&coord) {
+                candidates[y as usize][x as usize] = candidates_for(board, &coord);
+            }
+        }
+    }
+
+    candidates
+}
+
+/// The pencil marks for `coord`: the complement over `1..=SIDE` of everything
+/// already used in its row, column and block.
+fn candidates_for<const N: usize>(board: &Board<N>, coord: &Coord) -> SudokuHashSet<N> {
+    let row = BoardGenerator::<ThreadRng, N>::get_numbers_for_tiles(board.get_row_for_coord(coord));
+    let column = BoardGenerator::<ThreadRng, N>::get_numbers_for_tiles(board.get_column_for_coord(coord));
+    let block = BoardGenerator::<ThreadRng, N>::get_numbers_for_tiles(board.get_block_for_coord(coord));
+
+    row.union(&column).union(&block).complement()
+}
+
+fn find_naked_single<const N: usize>(candidates: &Candidates<N>) -> Option<(Coord, u8)> {
+    for (y, row) in candidates.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            if cell.len() == 1 {
+                return Some((Coord::new(x as u8, y as u8), cell.iter().next().unwrap()));
+            }
+        }
+    }
+    None
+}
+
+fn find_hidden_single<const N: usize>(board: &Board<N>, candidates: &Candidates<N>) -> Option<(Coord, u8)> {
+    for unit in units::<N>() {
+        for value in 1..=Board::<N>::SIDE as u8 {
+            if unit.iter().any(|coord| matches!(board.get_tile(coord), Tile::Filled(v) if *v == value)) {
+                continue;
+            }
+
+            let mut placements = unit.iter().filter(|coord| {
+                candidates[coord.y as usize][coord.x as usize].contains(value)
+            });
+
+            if let Some(coord) = placements.next() {
+                if placements.next().is_none() {
+                    return Some((*coord, value));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Eliminate the values of every naked pair (two cells in a unit sharing the
+/// same two candidates) from the rest of that unit. Returns whether anything
+/// was removed.
+fn eliminate_naked_pairs<const N: usize>(candidates: &mut Candidates<N>) -> bool {
+    let mut changed = false;
+
+    for unit in units::<N>() {
+        let pairs: Vec<(Coord, SudokuHashSet<N>)> = unit
+            .iter()
+            .filter(|coord| candidates[coord.y as usize][coord.x as usize].len() == 2)
+            .map(|coord| (*coord, candidates[coord.y as usize][coord.x as usize]))
+            .collect();
+
+        for i in 0..pairs.len() {
+            for j in (i + 1)..pairs.len() {
+                if pairs[i].1 != pairs[j].1 {
+                    continue;
+                }
+
+                let values = pairs[i].1;
+                for coord in &unit {
+                    if *coord == pairs[i].0 || *coord == pairs[j].0 {
+                        continue;
+                    }
+
+                    let cell = &mut candidates[coord.y as usize][coord.x as usize];
+                    if !cell.intersection(&values).is_empty() {
+                        *cell = cell.difference(&values);
+                        changed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    changed
+}
+
+/// The coordinates making up every row, column and block of the board.
+fn units<const N: usize>() -> Vec<Vec<Coord>> {
+    let side = Board::<N>::SIDE as u8;
+    let mut units = Vec::with_capacity(side as usize * 3);
+
+    for row in 0..side {
+        units.push((0..side).map(|x| Coord::new(x, row)).collect());
+    }
+    for col in 0..side {
+        units.push((0..side).map(|y| Coord::new(col, y)).collect());
+    }
+    for block in 0..side as usize {
+        let mut cells = Vec::with_capacity(side as usize);
+        for y in 0..side {
+            for x in 0..side {
+                let coord = Coord::new(x, y);
+                if coord.to_block_index::<N>() == block {
+                    cells.push(coord);
+                }
+            }
+        }
+        units.push(cells);
+    }
+
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_naked_singles_to_completion() {
+        // Each blank is the only gap in its row and column, so every fill is a naked single.
+        let board: Board<3> = "\
+            .34678912\
+            6.2195348\
+            19.342567\
+            859.61423\
+            4268.3791\
+            71392.856\
+            961537.84\
+            2874196.5\
+            34528617.".parse().unwrap();
+
+        let (solved, difficulty) = solve_logically(&board);
+
+        assert!(solved.is_complete());
+        assert!(solved.verify_board());
+        assert_eq!(
+            solved.to_string(),
+            "534678912672195348198342567859761423426853791713924856961537284287419635345286179"
+        );
+        assert_eq!(difficulty, Difficulty::NakedSingle);
+    }
+
+    #[test]
+    fn reports_guessing_when_propagation_stalls() {
+        let (_, difficulty) = solve_logically(&Board::<3>::default());
+        assert_eq!(difficulty, Difficulty::RequiresGuessing);
+    }
+
+    #[test]
+    fn solves_a_four_by_four_grid() {
+        // A 4x4 puzzle (N = 2) with one gap per row and column, solved by singles.
+        let board: Board<2> = ".2343.1221.3432.".parse().unwrap();
+        let (solved, _) = solve_logically(&board);
+
+        assert!(solved.is_complete());
+        assert!(solved.verify_board());
+    }
+}
@@ -1,14 +1,120 @@
 use std::error::Error;
-use crate::engine::game::Game;
+use std::io::{self, Write};
+use std::time::Instant;
+
+use crate::engine::board::Coord;
+use crate::engine::game::{Game, MoveOutcome};
 
 mod engine;
 
+const MAX_TRIES: usize = 10;
+const CELLS_GIVEN: usize = 30;
+
 fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello, world!");
+    println!("Sudoku. Commands: set <row> <col> <value>, undo, redo, hint, new, print, save, load <current> <solved>, quit");
+
+    let mut game = Game::<3>::new_random(MAX_TRIES, CELLS_GIVEN)?;
+    let mut started = Instant::now();
+    let mut mistakes = 0u32;
+
+    println!("Difficulty: {:?}", game.difficulty);
+    print_board(&game);
+
+    let stdin = io::stdin();
+    let mut line = String::new();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
 
-    let game = Game::new_random(2, 25)?;
+        line.clear();
+        if stdin.read_line(&mut line)? == 0 {
+            break;
+        }
 
-    println!("{:?}", game);
+        let mut parts = line.split_whitespace();
+        match parts.next() {
+            Some("set") => {
+                match (parse_value(parts.next()), parse_value(parts.next()), parse_value(parts.next())) {
+                    (Some(row), Some(col), Some(value)) => {
+                        match game.set_cell(Coord::new(col, row), value) {
+                            MoveOutcome::Placed => print_board(&game),
+                            MoveOutcome::Conflict => {
+                                mistakes += 1;
+                                println!("That conflicts with another cell. Mistakes: {}", mistakes);
+                                print_board(&game);
+                            }
+                            MoveOutcome::Solved => {
+                                print_board(&game);
+                                println!("Solved in {:?} with {} mistakes!", started.elapsed(), mistakes);
+                            }
+                        }
+                    }
+                    _ => println!("Usage: set <row> <col> <value>"),
+                }
+            }
+            Some("undo") => {
+                if !game.undo() {
+                    println!("Nothing to undo.");
+                }
+                print_board(&game);
+            }
+            Some("redo") => {
+                if !game.redo() {
+                    println!("Nothing to redo.");
+                }
+                print_board(&game);
+            }
+            Some("hint") => {
+                match game.hint() {
+                    Some(coord) => println!("Revealed row {} col {}.", coord.y, coord.x),
+                    None => println!("The board is already complete."),
+                }
+                if game.is_solved() {
+                    println!("Solved in {:?} with {} mistakes!", started.elapsed(), mistakes);
+                }
+                print_board(&game);
+            }
+            Some("new") => {
+                game = Game::<3>::new_random(MAX_TRIES, CELLS_GIVEN)?;
+                started = Instant::now();
+                mistakes = 0;
+                println!("Difficulty: {:?}", game.difficulty);
+                print_board(&game);
+            }
+            Some("print") => print_board(&game),
+            Some("save") => {
+                let (current, solved) = game.to_strings();
+                println!("{}", current);
+                println!("{}", solved);
+            }
+            Some("load") => {
+                match (parts.next(), parts.next()) {
+                    (Some(current), Some(solved)) => match Game::<3>::from_strings(current, solved) {
+                        Ok(loaded) => {
+                            game = loaded;
+                            started = Instant::now();
+                            mistakes = 0;
+                            println!("Difficulty: {:?}", game.difficulty);
+                            print_board(&game);
+                        }
+                        Err(err) => println!("Couldn't load that puzzle: {}", err),
+                    },
+                    _ => println!("Usage: load <current> <solved>"),
+                }
+            }
+            Some("quit") | Some("exit") => break,
+            Some(other) => println!("Unknown command: {}", other),
+            None => {}
+        }
+    }
 
     Ok(())
 }
+
+fn parse_value(raw: Option<&str>) -> Option<u8> {
+    raw?.parse().ok()
+}
+
+fn print_board(game: &Game<3>) {
+    println!("{:?}", game.current);
+}